@@ -2,10 +2,13 @@
 //! The core functionality, along with unit tests, is implemented in Rust to ensure correctness.
 //! The Python bindings are implemented in the `lib.rs` file.
 
+use std::collections::{HashMap, HashSet};
 use std::ops::Index;
 
+use aho_corasick::AhoCorasick;
 use rayon::prelude::*;
 use regex::Regex;
+use regex_syntax::hir::{Hir, HirKind};
 
 /// An enum representing the output of a regex compilation.
 pub enum RegexResult {
@@ -43,11 +46,17 @@ impl RegexResult {
 /// The regex is used to capture a PGN game block that begins with `[Event` and ends
 /// with a game result (e.g., "1-0", "0-1", or "1/2-1/2").
 ///
+/// The tag section and the movetext are separated with `\n\n` before the result
+/// token is searched for, so a `[Result "..."]` header tag (part of the Seven Tag
+/// Roster, and present in essentially every real PGN file) is never mistaken for
+/// the terminator: the search for the result token only starts once the blank
+/// line that ends the tag block has been consumed.
+///
 /// # Errors
 ///
 /// Returns a `regex::Error` if the pattern is invalid.
 pub fn get_regex() -> RegexResult {
-    match Regex::new(r"(?s)(\[Event.*?(?:1-0|0-1|1/2-1/2))") {
+    match Regex::new(r"(?s)(\[Event.*?\n\n.*?(?:1-0|0-1|1/2-1/2))") {
         Ok(re) => RegexResult::Compiled(re),
         Err(e) => RegexResult::Error(e),
     }
@@ -76,6 +85,352 @@ pub fn get_games(data: &str, re: &Regex) -> Vec<String> {
         .collect()
 }
 
+/// A single term in a [`filter_games_rs`] query, e.g. `Result:1-0` or `-White:Smith`.
+#[derive(Debug, Clone, PartialEq)]
+struct QueryTerm {
+    tag: String,
+    value: String,
+}
+
+/// A query parsed into its three term lists: `all` must hold, `none` must not hold,
+/// and at least one of `any` must hold (when `any` is non-empty).
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Query {
+    all: Vec<QueryTerm>,
+    none: Vec<QueryTerm>,
+    any: Vec<QueryTerm>,
+}
+
+/// Splits a query string into whitespace-separated terms, treating `"..."` as a
+/// single term so values like `Moves:"1. e4"` survive intact.
+fn tokenize_query(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut token = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                break;
+            }
+            if c == '"' {
+                chars.next();
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        break;
+                    }
+                    token.push(c);
+                }
+            } else {
+                token.push(c);
+                chars.next();
+            }
+        }
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Parses a query string of whitespace-separated `TagName:value` terms into a [`Query`].
+///
+/// A plain term means the game's tag must equal (or contain) that value, a `-` prefix
+/// means it must NOT, and `+`-prefixed terms form an OR-group where the game must
+/// satisfy at least one of them.
+fn parse_query(query: &str) -> Query {
+    let mut parsed = Query::default();
+    for token in tokenize_query(query) {
+        let (mode, rest) = if let Some(rest) = token.strip_prefix('-') {
+            ('-', rest)
+        } else if let Some(rest) = token.strip_prefix('+') {
+            ('+', rest)
+        } else {
+            (' ', token.as_str())
+        };
+        let Some((tag, value)) = rest.split_once(':') else {
+            continue;
+        };
+        let term = QueryTerm {
+            tag: tag.to_string(),
+            value: value.to_string(),
+        };
+        match mode {
+            '-' => parsed.none.push(term),
+            '+' => parsed.any.push(term),
+            _ => parsed.all.push(term),
+        }
+    }
+    parsed
+}
+
+/// Builds a lowercase tag-name -> value map for a single game, including the
+/// synthetic `moves` pseudo-tag so queries can filter on `Moves:...`.
+///
+/// The `moves` value is rendered from the [`parse_movetext_rs`] AST (via
+/// [`render_movetext_rs`]) rather than the flat [`extract_moves_rs`] string, so
+/// it reflects the parser's more correct comment/variation handling.
+fn game_tag_map(game: &str) -> HashMap<String, String> {
+    let mut map: HashMap<String, String> = extract_tags_rs(game)
+        .into_iter()
+        .map(|tag| (tag.name.to_lowercase(), tag.value))
+        .collect();
+    let movetext = parse_movetext_rs(&extract_moves_rs(game).value).unwrap_or_default();
+    map.insert("moves".to_string(), render_movetext_rs(&movetext));
+    map
+}
+
+/// Returns whether `term` holds against `map`: the tag is looked up case-insensitively
+/// and the term's value is matched as a case-insensitive substring of the tag's value.
+fn term_matches(map: &HashMap<String, String>, term: &QueryTerm) -> bool {
+    match map.get(&term.tag.to_lowercase()) {
+        Some(value) => value
+            .to_lowercase()
+            .contains(&term.value.to_lowercase()),
+        None => false,
+    }
+}
+
+fn query_matches(map: &HashMap<String, String>, query: &Query) -> bool {
+    if query.all.iter().any(|term| !term_matches(map, term)) {
+        return false;
+    }
+    if query.none.iter().any(|term| term_matches(map, term)) {
+        return false;
+    }
+    if !query.any.is_empty() && !query.any.iter().any(|term| term_matches(map, term)) {
+        return false;
+    }
+    true
+}
+
+/// Filters a multi-game PGN blob down to the games matching `query`.
+///
+/// `query` is a small filter DSL: whitespace-separated terms of the form
+/// `TagName:value` (the tag must contain `value`), `-TagName:value` (the tag must
+/// NOT contain `value`), and `+TagName:value` (an OR-group; the game must match at
+/// least one `+` term if any are present). Tag names are matched case-insensitively
+/// and values as case-insensitive substrings. A special `Moves` pseudo-tag matches
+/// against the [`parse_movetext_rs`] tree rendered back to a string (see
+/// [`render_movetext_rs`]), so callers can filter by opening, e.g. `Moves:"1. e4"`.
+///
+/// # Arguments
+///
+/// * `data` - A string slice containing one or more PGN games.
+/// * `query` - The filter DSL query string.
+/// * `re` - A reference to a compiled regex for capturing game blocks.
+///
+/// # Examples
+///
+/// ```
+/// let re = get_regex().unwrap();
+/// let carlsen_wins = filter_games_rs(pgn_data, "White:Carlsen Result:1-0", &re);
+/// ```
+pub fn filter_games_rs(data: &str, query: &str, re: &Regex) -> Vec<String> {
+    let parsed = parse_query(query);
+    get_games(data, re)
+        .into_par_iter()
+        .filter(|game| query_matches(&game_tag_map(game), &parsed))
+        .collect()
+}
+
+/// The shortest literal considered selective enough to prefilter on. Shorter
+/// literals (e.g. a single piece letter) appear in almost every game and would
+/// reject almost nothing, so they are dropped and their pattern always runs.
+const MIN_LITERAL_LEN: usize = 3;
+
+/// A boolean formula over literal ids, built once per pattern, that says which
+/// literals must be present in a game's text before the pattern's regex could
+/// possibly match it. Mirrors the "required substrings" analysis in RE2's
+/// FilteredRE2: an `Or` of `And` groups is the regex's top-level alternation
+/// of required-literal sequences; `Always` means no selective literal was found
+/// and the pattern must just be run on every game.
+#[derive(Debug, Clone, PartialEq)]
+enum LiteralFormula {
+    Always,
+    Or(Vec<Vec<usize>>),
+}
+
+/// Looks up `literal`'s id, interning it into `literals`/`index` if this is the
+/// first time it has been seen across any pattern in the set.
+fn intern_literal(literal: &str, literals: &mut Vec<String>, index: &mut HashMap<String, usize>) -> usize {
+    if let Some(&id) = index.get(literal) {
+        return id;
+    }
+    let id = literals.len();
+    literals.push(literal.to_string());
+    index.insert(literal.to_string(), id);
+    id
+}
+
+/// Walks a (sub-)tree of a pattern's parsed [`Hir`], collecting the literal ids that
+/// are unconditionally required by that tree into `required`. Classes, repetitions,
+/// anchors and the like contribute nothing: they don't guarantee a substring, so
+/// they're simply left out of the formula rather than making it unsatisfiable.
+fn collect_required_literals(
+    hir: &Hir,
+    literals: &mut Vec<String>,
+    index: &mut HashMap<String, usize>,
+    required: &mut Vec<usize>,
+) {
+    match hir.kind() {
+        HirKind::Literal(lit) => {
+            if let Ok(s) = std::str::from_utf8(&lit.0) {
+                if s.len() >= MIN_LITERAL_LEN {
+                    required.push(intern_literal(s, literals, index));
+                }
+            }
+        }
+        HirKind::Capture(capture) => {
+            collect_required_literals(&capture.sub, literals, index, required);
+        }
+        HirKind::Concat(subs) => {
+            for sub in subs {
+                collect_required_literals(sub, literals, index, required);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Extracts a [`LiteralFormula`] for a parsed pattern. A top-level alternation
+/// becomes an `Or` of each branch's required-literal sequence; anything else is
+/// treated as a single sequence and becomes an `Or` of one `And` group.
+fn extract_formula(
+    hir: &Hir,
+    literals: &mut Vec<String>,
+    index: &mut HashMap<String, usize>,
+) -> LiteralFormula {
+    if let HirKind::Alternation(subs) = hir.kind() {
+        let mut branches = Vec::with_capacity(subs.len());
+        for sub in subs {
+            let mut required = Vec::new();
+            collect_required_literals(sub, literals, index, &mut required);
+            branches.push(required);
+        }
+        return LiteralFormula::Or(branches);
+    }
+
+    let mut required = Vec::new();
+    collect_required_literals(hir, literals, index, &mut required);
+    LiteralFormula::Or(vec![required])
+}
+
+/// Returns whether `formula` could possibly be satisfied given the set of literal
+/// ids known to be present in a game (`Always`, or any `And` group fully covered).
+fn formula_satisfiable(formula: &LiteralFormula, present: &HashSet<usize>) -> bool {
+    match formula {
+        LiteralFormula::Always => true,
+        LiteralFormula::Or(branches) => branches
+            .iter()
+            .any(|and_group| and_group.iter().all(|id| present.contains(id))),
+    }
+}
+
+/// A set of regex patterns paired with a literal prefilter, so that matching many
+/// patterns against many games skips the patterns that provably cannot match a
+/// given game without running their (comparatively expensive) full regex.
+///
+/// Inspired by RE2's `FilteredRE2`: each pattern is statically reduced to a boolean
+/// formula of required literal substrings, every distinct literal across the whole
+/// set is compiled into a single Aho-Corasick automaton, and a game is scanned with
+/// that automaton once to learn which literals it contains. Only the patterns whose
+/// formula is satisfied by that set are actually run against the game.
+pub struct PatternSet {
+    regexes: Vec<Regex>,
+    formulas: Vec<LiteralFormula>,
+    automaton: Option<AhoCorasick>,
+}
+
+impl PatternSet {
+    /// Compiles `patterns` into a `PatternSet`, extracting a required-literal
+    /// formula for each one and building the shared Aho-Corasick automaton.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any pattern fails to compile as a regex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let set = PatternSet::new(&[r"Carlsen", r"1\-0|0\-1"]);
+    /// ```
+    pub fn new(patterns: &[&str]) -> Self {
+        let mut literals = Vec::new();
+        let mut index = HashMap::new();
+        let mut regexes = Vec::with_capacity(patterns.len());
+        let mut formulas = Vec::with_capacity(patterns.len());
+
+        for pattern in patterns {
+            let regex = Regex::new(pattern)
+                .unwrap_or_else(|e| panic!("invalid pattern {:?}: {}", pattern, e));
+            let formula = match regex_syntax::Parser::new().parse(pattern) {
+                Ok(hir) => extract_formula(&hir, &mut literals, &mut index),
+                Err(_) => LiteralFormula::Always,
+            };
+            regexes.push(regex);
+            formulas.push(formula);
+        }
+
+        let automaton = if literals.is_empty() {
+            None
+        } else {
+            AhoCorasick::new(&literals).ok()
+        };
+
+        Self {
+            regexes,
+            formulas,
+            automaton,
+        }
+    }
+
+    /// Scans `data` for games (using `re`, the same game-capturing regex as
+    /// [`get_games`]) and returns every `(pattern_index, game_text)` pair where
+    /// that pattern's regex actually matches the game.
+    ///
+    /// For each game, the shared Aho-Corasick automaton runs once to find which
+    /// required literals are present; only patterns whose formula is satisfiable
+    /// from that set (or that have no selective literal, and so always run) pay
+    /// for a full regex match. Composes with the existing `par_iter` parallelism
+    /// used elsewhere in this module: games are scanned concurrently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let re = get_regex().unwrap();
+    /// let set = PatternSet::new(&[r"Carlsen"]);
+    /// let hits = set.matching_games(pgn_data, &re);
+    /// ```
+    pub fn matching_games(&self, data: &str, re: &Regex) -> Vec<(usize, String)> {
+        get_games(data, re)
+            .into_par_iter()
+            .flat_map(|game| {
+                // Overlapping matches, not just non-overlapping ones: with both
+                // "carl" and "carlsen" interned, a non-overlapping scan of
+                // "carlsen" would report only the first literal that wins at
+                // that position and silently drop "carlsen" from `present`.
+                let present: HashSet<usize> = match &self.automaton {
+                    Some(automaton) => automaton
+                        .find_overlapping_iter(&game)
+                        .map(|m| m.pattern().as_usize())
+                        .collect(),
+                    None => HashSet::new(),
+                };
+
+                self.formulas
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, formula)| formula_satisfiable(formula, &present))
+                    .filter(|(i, _)| self.regexes[*i].is_match(&game))
+                    .map(|(i, _)| (i, game.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
 /// Represents a PGN tag, formatted as `[TagName "TagValue"]`.
 #[derive(Debug, PartialEq)]
 pub struct Tag {
@@ -205,21 +560,363 @@ pub fn extract_moves_rs(game: &str) -> Tag {
 /// Extracts the result tag from a PGN game.
 ///
 /// The result tag is expected to be the last untagged statement in the game,
-/// and is one of the following: "1-0", "0-1", "1/2-1/2", or "*", for white win, 
+/// and is one of the following: "1-0", "0-1", "1/2-1/2", or "*", for white win,
 /// black win, draw, or undecided, respectively.
 /// This function returns the string as a `Tag` with the name "Result", with value
 /// that result token of "1-0", "0-1", "1/2-1/2", or "*".
 pub fn extract_result_rs(game: &str) -> Tag {
-    let re = Regex::new(r#"(\s+)([0\-1|1\-0|1/2\-1/2])"#).unwrap();
-    // Find the last result token in the game.
-    let result = re
-        .captures_iter(game)
-        .last()
-        .map(|cap| cap.get(2).unwrap().as_str())
+    // Strip `{...}` comments before anything else: PGN brace comments do not
+    // nest and can contain a `;` on the same line as the real result token
+    // (e.g. `{Black resigns; nice mating net} 1-0`), so truncating at `;`
+    // first would eat the result along with the rest of the comment.
+    let re_comment = Regex::new(r"\{[^}]*\}").unwrap();
+    let without_braces = re_comment.replace_all(game, " ");
+
+    let moves_str: String = without_braces
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('['))
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        // A `;` comment runs to the end of its line, so drop it before joining.
+        .map(|line| match line.find(';') {
+            Some(idx) => &line[..idx],
+            None => line,
+        })
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    // Scan from the end for the last genuine terminator token, so trailing
+    // annotations (comments, NAGs) after the result don't throw this off.
+    let result = moves_str
+        .split_whitespace()
+        .rev()
+        .find(|token| RESULT_TOKENS.contains(token))
         .unwrap_or("*");
+
     Tag::new("Result", result)
 }
 
+/// A mismatch between a game's `[Result "..."]` header tag and the token that
+/// actually terminates its movetext.
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+    pub header: String,
+    pub movetext: String,
+}
+
+/// Compares the `[Result "..."]` header tag (via [`extract_tags_rs`]) against the
+/// token that actually terminates the movetext (via [`extract_result_rs`]),
+/// flagging games where an imported database's header and scoreline conflict.
+///
+/// # Errors
+///
+/// Returns `Err(Mismatch)` with both values when the header tag disagrees with
+/// the movetext's terminating token. A missing `Result` tag is not a conflict by
+/// itself, since plenty of PGN sources omit the header tag; this falls through to
+/// `Ok` using the movetext's own terminator.
+///
+/// # Examples
+///
+/// ```
+/// let result = validate_result_rs(pgn_game);
+/// assert!(result.is_ok());
+/// ```
+pub fn validate_result_rs(game: &str) -> Result<Tag, Mismatch> {
+    let header = extract_tags_rs(game)
+        .into_iter()
+        .find(|tag| tag.name == "Result")
+        .map(|tag| tag.value);
+    let movetext_result = extract_result_rs(game).value;
+
+    match header {
+        Some(header) if header != movetext_result => Err(Mismatch {
+            header,
+            movetext: movetext_result,
+        }),
+        _ => Ok(Tag::new("Result", &movetext_result)),
+    }
+}
+
+/// An element of a parsed movetext tree.
+///
+/// Variations (`(...)`) nest to arbitrary depth by holding their own `Vec<Element>`,
+/// which is what makes this representation strictly more useful than the flat,
+/// comment-stripped string that [`extract_moves_rs`] produces.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Element {
+    /// A move number, e.g. `1.` or `1...`. `black` is `true` for the `...` (black-to-move) form.
+    MoveNumber { num: u32, black: bool },
+    /// A SAN move token, e.g. `e4`, `Nf3`, `O-O`.
+    San(String),
+    /// A Numeric Annotation Glyph, e.g. `$1`.
+    Nag(u16),
+    /// Text from a `{...}` brace comment, with the braces stripped.
+    Comment(String),
+    /// A parenthesized variation, holding its own nested movetext.
+    Variation(Vec<Element>),
+}
+
+/// A parsed movetext tree: the sequence of elements between the tag section and the result.
+pub type MoveText = Vec<Element>;
+
+/// An error produced while parsing movetext.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MovetextError {
+    /// A `(` was never closed by a matching `)`.
+    UnbalancedParens,
+    /// A `{` was never closed by a matching `}`.
+    UnbalancedBraces,
+}
+
+const RESULT_TOKENS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+/// Parses PGN movetext into a [`MoveText`] tree.
+///
+/// This is a recursive-descent parser: a `(` opens a new [`Element::Variation`] and
+/// recurses, a matching `)` closes it, so variations nest to arbitrary depth. `{...}`
+/// becomes a [`Element::Comment`] (PGN brace comments do not nest, so the first `}`
+/// closes it), `;` comments consume to end of line, `$123` becomes an [`Element::Nag`],
+/// and a run of digits followed by `.` or `...` becomes an [`Element::MoveNumber`].
+/// Everything else is treated as a SAN token. Parsing stops at the trailing result
+/// token (`1-0`, `0-1`, `1/2-1/2`, or `*`), which is not included in the tree.
+///
+/// # Errors
+///
+/// Returns [`MovetextError::UnbalancedParens`] or [`MovetextError::UnbalancedBraces`]
+/// if the movetext contains a `(` or `{` with no matching closer, rather than panicking.
+///
+/// # Examples
+///
+/// ```
+/// let tree = parse_movetext_rs("1. e4 e5 (1... c5) 1-0").unwrap();
+/// assert_eq!(tree.len(), 3);
+/// ```
+pub fn parse_movetext_rs(movetext: &str) -> Result<MoveText, MovetextError> {
+    let mut chars = movetext.chars().peekable();
+    let (elements, unmatched_close) = parse_elements(&mut chars)?;
+    if unmatched_close {
+        return Err(MovetextError::UnbalancedParens);
+    }
+    Ok(elements)
+}
+
+/// Parses a single level of movetext, returning its elements and whether the level
+/// was terminated by an unmatched `)` (which the caller must treat as balanced only
+/// if it is itself inside a variation).
+fn parse_elements(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+) -> Result<(Vec<Element>, bool), MovetextError> {
+    let mut elements = Vec::new();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '(' => {
+                chars.next();
+                let (inner, closed) = parse_elements(chars)?;
+                if !closed {
+                    return Err(MovetextError::UnbalancedParens);
+                }
+                elements.push(Element::Variation(inner));
+            }
+            ')' => {
+                chars.next();
+                return Ok((elements, true));
+            }
+            '{' => {
+                chars.next();
+                let mut comment = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    comment.push(c);
+                }
+                if !closed {
+                    return Err(MovetextError::UnbalancedBraces);
+                }
+                elements.push(Element::Comment(comment.trim().to_string()));
+            }
+            ';' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '$' => {
+                chars.next();
+                let digits = take_while(chars, |d| d.is_ascii_digit());
+                if let Ok(num) = digits.parse::<u16>() {
+                    elements.push(Element::Nag(num));
+                }
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            _ => {
+                let token = take_while(chars, |d| !d.is_whitespace() && !"(){};$".contains(d));
+                if token.is_empty() {
+                    // Lone stray character from the "(){};$" set that didn't match
+                    // a case above (shouldn't happen); skip it to guarantee progress.
+                    chars.next();
+                    continue;
+                }
+                if RESULT_TOKENS.contains(&token.as_str()) {
+                    return Ok((elements, false));
+                }
+                if let Some((num, black)) = parse_move_number(&token) {
+                    elements.push(Element::MoveNumber { num, black });
+                } else {
+                    elements.push(Element::San(token));
+                }
+            }
+        }
+    }
+
+    Ok((elements, false))
+}
+
+/// Consumes and returns the longest prefix of `chars` matching `pred`, without
+/// consuming the first character that fails it.
+fn take_while(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+    pred: impl Fn(char) -> bool,
+) -> String {
+    let mut out = String::new();
+    while let Some(&c) = chars.peek() {
+        if !pred(c) {
+            break;
+        }
+        out.push(c);
+        chars.next();
+    }
+    out
+}
+
+/// Parses a token like `1.` or `12...` into `(move number, is black-to-move)`.
+/// Returns `None` if the token is not a digit run followed by one or more dots.
+fn parse_move_number(token: &str) -> Option<(u32, bool)> {
+    let digits_end = token.find(|c: char| !c.is_ascii_digit())?;
+    if digits_end == 0 {
+        return None;
+    }
+    let (digits, dots) = token.split_at(digits_end);
+    if dots.is_empty() || !dots.chars().all(|c| c == '.') {
+        return None;
+    }
+    digits.parse::<u32>().ok().map(|num| (num, dots.len() >= 3))
+}
+
+/// Renders a parsed movetext tree back into a flat move string, recursing into
+/// variations (wrapped in parens) so substring queries like the `Moves`
+/// filter-DSL pseudo-tag can match an opening nested inside a variation, not
+/// just the mainline. NAGs and comments carry no move text and are dropped.
+pub fn render_movetext_rs(movetext: &MoveText) -> String {
+    movetext
+        .iter()
+        .filter_map(|el| match el {
+            Element::MoveNumber { num, black } => {
+                Some(format!("{}{}", num, if *black { "..." } else { "." }))
+            }
+            Element::San(san) => Some(san.clone()),
+            Element::Variation(inner) => Some(format!("({})", render_movetext_rs(inner))),
+            Element::Nag(_) | Element::Comment(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A parsed PGN game: the Seven Tag Roster as typed fields, any remaining tags,
+/// and the parsed movetext.
+///
+/// The roster fields (`event`, `site`, `date`, `round`, `white`, `black`, `result`)
+/// default to `"?"` when the corresponding tag is absent, per the PGN spec, except
+/// `result` which falls back to whatever [`extract_result_rs`] finds in the
+/// movetext (and then `"*"`) so it stays accurate even for headerless games.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    pub event: String,
+    pub site: String,
+    pub date: String,
+    pub round: String,
+    pub white: String,
+    pub black: String,
+    pub result: String,
+    /// Tags outside the Seven Tag Roster, keyed by tag name.
+    pub tags: HashMap<String, String>,
+    pub moves: MoveText,
+}
+
+const UNKNOWN_TAG: &str = "?";
+
+/// Parses a single PGN game into a [`Game`], combining [`extract_tags_rs`], the
+/// movetext parser ([`parse_movetext_rs`]), and [`extract_result_rs`].
+///
+/// Movetext that fails to parse (unbalanced parens or braces) yields an empty
+/// move list rather than propagating the error, since a `Game` should always be
+/// constructible from anything [`get_games`] handed back.
+///
+/// # Examples
+///
+/// ```
+/// let game = parse_game_rs(pgn_game);
+/// assert_eq!(game.white, "White Player");
+/// ```
+pub fn parse_game_rs(game: &str) -> Game {
+    let mut tags: HashMap<String, String> = extract_tags_rs(game)
+        .into_iter()
+        .map(|tag| (tag.name, tag.value))
+        .collect();
+
+    let mut take_roster_tag = |name: &str| tags.remove(name).unwrap_or_else(|| UNKNOWN_TAG.to_string());
+
+    let event = take_roster_tag("Event");
+    let site = take_roster_tag("Site");
+    let date = take_roster_tag("Date");
+    let round = take_roster_tag("Round");
+    let white = take_roster_tag("White");
+    let black = take_roster_tag("Black");
+    let result = tags
+        .remove("Result")
+        .unwrap_or_else(|| extract_result_rs(game).value);
+
+    let moves = parse_movetext_rs(&extract_moves_rs(game).value).unwrap_or_default();
+
+    Game {
+        event,
+        site,
+        date,
+        round,
+        white,
+        black,
+        result,
+        tags,
+        moves,
+    }
+}
+
+/// Parses every game out of a multi-game PGN blob, built on [`get_games`].
+///
+/// # Arguments
+///
+/// * `data` - A string slice containing one or more PGN games.
+/// * `re` - A reference to a compiled regex for capturing game blocks.
+///
+/// # Examples
+///
+/// ```
+/// let re = get_regex().unwrap();
+/// let games = parse_all_rs(pgn_data, &re);
+/// ```
+pub fn parse_all_rs(data: &str, re: &Regex) -> Vec<Game> {
+    get_games(data, re)
+        .into_par_iter()
+        .map(|game| parse_game_rs(&game))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -360,4 +1057,336 @@ mod tests {
         assert_eq!(result, Tag::new("Result", "1-0"));
     }
 
+    #[test]
+    fn can_extract_each_result_token() {
+        for token in ["1-0", "0-1", "1/2-1/2", "*"] {
+            let game = format!("[Event \"Test\"]\n\n1. e4 e5 {}", token);
+            assert_eq!(extract_result_rs(&game), Tag::new("Result", token));
+        }
+    }
+
+    #[test]
+    fn extract_result_does_not_match_single_characters() {
+        // Regression test: the old regex `[0\-1|1\-0|1/2\-1/2]` was a character
+        // class, not an alternation, so it matched lone characters like "0" or "-".
+        let game = "[Event \"Test\"]\n\n1. e4 e5 2. d4-d5 *";
+        assert_eq!(extract_result_rs(game), Tag::new("Result", "*"));
+    }
+
+    #[test]
+    fn extract_result_handles_a_semicolon_inside_a_brace_comment() {
+        // Regression test: truncating at `;` before stripping `{...}` comments
+        // ate the rest of the line, including the real result token, whenever
+        // a brace comment containing a `;` shared a line with the terminator.
+        let game = "[Event \"Test\"]\n\n10. Qxf7# {Black resigns; nice mating net} 1-0";
+        assert_eq!(extract_result_rs(game), Tag::new("Result", "1-0"));
+    }
+
+    #[test]
+    fn validate_result_ok_when_header_and_movetext_agree() {
+        let game = r#"[Event "Test Game 1"]
+[Result "1-0"]
+
+1. e4 e5 1-0"#;
+        assert_eq!(validate_result_rs(game), Ok(Tag::new("Result", "1-0")));
+    }
+
+    #[test]
+    fn validate_result_flags_a_mismatch() {
+        let game = r#"[Event "Test Game 1"]
+[Result "1-0"]
+
+1. e4 e5 0-1"#;
+        assert_eq!(
+            validate_result_rs(game),
+            Err(Mismatch {
+                header: "1-0".to_string(),
+                movetext: "0-1".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn extract_result_ignores_result_tokens_inside_line_comments() {
+        let game = "[Event \"Test\"]\n\n1. e4 e5 ; engine eval favored 0-1 earlier\n2. Nf3 1-0";
+        assert_eq!(extract_result_rs(game), Tag::new("Result", "1-0"));
+    }
+
+    #[test]
+    fn validate_result_is_ok_when_header_is_missing() {
+        let game = "[Event \"Test Game 1\"]\n\n1. e4 e5 *";
+        assert_eq!(validate_result_rs(game), Ok(Tag::new("Result", "*")));
+    }
+
+    #[test]
+    fn can_parse_simple_movetext() {
+        let tree = parse_movetext_rs("1. e4 e5 2. Nf3 1-0").unwrap();
+        assert_eq!(
+            tree,
+            vec![
+                Element::MoveNumber { num: 1, black: false },
+                Element::San("e4".to_string()),
+                Element::San("e5".to_string()),
+                Element::MoveNumber { num: 2, black: false },
+                Element::San("Nf3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_parse_nested_variations() {
+        let tree = parse_movetext_rs("1. e4 e5 (1... c5 (1... e6)) 2. Nf3 1-0").unwrap();
+        let variation = match &tree[3] {
+            Element::Variation(v) => v,
+            other => panic!("expected a variation, got {:?}", other),
+        };
+        assert_eq!(
+            variation[0],
+            Element::MoveNumber { num: 1, black: true }
+        );
+        assert_eq!(variation[1], Element::San("c5".to_string()));
+        match &variation[2] {
+            Element::Variation(inner) => {
+                assert_eq!(inner[0], Element::MoveNumber { num: 1, black: true });
+                assert_eq!(inner[1], Element::San("e6".to_string()));
+            }
+            other => panic!("expected a nested variation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn can_parse_comments_nags_and_line_comments() {
+        let tree =
+            parse_movetext_rs("1. e4 {best by test} e5 $1 ; trailing remark\n2. Nf3 *").unwrap();
+        assert_eq!(
+            tree,
+            vec![
+                Element::MoveNumber { num: 1, black: false },
+                Element::San("e4".to_string()),
+                Element::Comment("best by test".to_string()),
+                Element::San("e5".to_string()),
+                Element::Nag(1),
+                Element::MoveNumber { num: 2, black: false },
+                Element::San("Nf3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unbalanced_parens_is_an_error_not_a_panic() {
+        let err = parse_movetext_rs("1. e4 e5 (1... c5 2. Nf3").unwrap_err();
+        assert_eq!(err, MovetextError::UnbalancedParens);
+    }
+
+    #[test]
+    fn unbalanced_braces_is_an_error_not_a_panic() {
+        let err = parse_movetext_rs("1. e4 {unterminated comment").unwrap_err();
+        assert_eq!(err, MovetextError::UnbalancedBraces);
+    }
+
+    #[test]
+    fn movetext_tree_re_derives_flat_moves() {
+        let game = r#"[Event "Test Game 1"]
+[Site "?"]
+[Date "2021.01.01"]
+
+1. e4 e5 (1... c5) 1-0"#;
+        let flat = extract_moves_rs(game);
+
+        let moves_only = extract_moves_rs(game).value;
+        let tree = parse_movetext_rs(&moves_only).unwrap();
+        let rebuilt: String = tree
+            .iter()
+            .filter_map(|el| match el {
+                Element::MoveNumber { num, black } => {
+                    Some(format!("{}{}", num, if *black { "..." } else { "." }))
+                }
+                Element::San(san) => Some(san.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        assert_eq!(rebuilt, "1. e4 e5");
+        assert_eq!(flat, Tag::new("Moves", "1. e4 e5 (1... c5)"));
+    }
+
+    // Includes a `[Result "..."]` header tag in every game, which is part of the
+    // Seven Tag Roster and present in virtually all real PGN files: it is what
+    // exposed the `get_regex` truncation bug (the header's result token was
+    // mistaken for the movetext's terminator), so it stays in the fixture to
+    // guard against a regression.
+    const FILTER_SAMPLE: &str = r#"[Event "Test Game 1"]
+[Site "?"]
+[White "Magnus Carlsen"]
+[Black "Hikaru Nakamura"]
+[Result "1-0"]
+
+1. e4 e5 1-0
+
+[Event "Test Game 2"]
+[Site "?"]
+[White "Magnus Carlsen"]
+[Black "Fabiano Caruana"]
+[Result "1/2-1/2"]
+
+1. d4 d5 1/2-1/2
+
+[Event "Test Game 3"]
+[Site "?"]
+[White "Hikaru Nakamura"]
+[Black "Magnus Carlsen"]
+[Result "0-1"]
+
+1. e4 c5 0-1
+"#;
+
+    #[test]
+    fn filter_games_matches_positive_term() {
+        let re = get_regex().expect("Failed to compile regex");
+        let games = filter_games_rs(FILTER_SAMPLE, "White:Carlsen", &re);
+        assert_eq!(games.len(), 2);
+    }
+
+    #[test]
+    fn filter_games_combines_positive_terms_with_and() {
+        let re = get_regex().expect("Failed to compile regex");
+        // The request's own headline example: Carlsen wins, i.e. White:Carlsen
+        // AND Result:1-0.
+        let games = filter_games_rs(FILTER_SAMPLE, "White:Carlsen Result:1-0", &re);
+        assert_eq!(games.len(), 1);
+        assert!(games[0].contains("Test Game 1"));
+    }
+
+    #[test]
+    fn filter_games_excludes_negative_term() {
+        let re = get_regex().expect("Failed to compile regex");
+        let games = filter_games_rs(FILTER_SAMPLE, "White:Carlsen -Result:1/2-1/2", &re);
+        assert_eq!(games.len(), 1);
+        assert!(games[0].contains("Test Game 1"));
+    }
+
+    #[test]
+    fn filter_games_or_group_matches_any() {
+        let re = get_regex().expect("Failed to compile regex");
+        // Carlsen wins that weren't draws: either side winning, i.e.
+        // Result:1-0 OR Result:0-1.
+        let games = filter_games_rs(FILTER_SAMPLE, "+Result:1-0 +Result:0-1", &re);
+        assert_eq!(games.len(), 2);
+    }
+
+    #[test]
+    fn filter_games_moves_pseudo_tag() {
+        let re = get_regex().expect("Failed to compile regex");
+        let games = filter_games_rs(FILTER_SAMPLE, r#"Moves:"1. e4""#, &re);
+        assert_eq!(games.len(), 2);
+    }
+
+    #[test]
+    fn pattern_set_matches_single_literal_pattern() {
+        let re = get_regex().expect("Failed to compile regex");
+        let set = PatternSet::new(&["Carlsen"]);
+        let hits = set.matching_games(FILTER_SAMPLE, &re);
+        assert_eq!(hits.len(), 3);
+        assert!(hits.iter().all(|(idx, _)| *idx == 0));
+    }
+
+    #[test]
+    fn pattern_set_distinguishes_multiple_patterns() {
+        let re = get_regex().expect("Failed to compile regex");
+        let set = PatternSet::new(&["Caruana", "Nakamura"]);
+        let hits = set.matching_games(FILTER_SAMPLE, &re);
+
+        let caruana_hits = hits.iter().filter(|(idx, _)| *idx == 0).count();
+        let nakamura_hits = hits.iter().filter(|(idx, _)| *idx == 1).count();
+        assert_eq!(caruana_hits, 1);
+        assert_eq!(nakamura_hits, 2);
+    }
+
+    #[test]
+    fn pattern_set_pattern_with_no_selective_literal_always_checks() {
+        let re = get_regex().expect("Failed to compile regex");
+        // `.` matches anywhere, so this pattern has no required literal and must
+        // fall back to being checked against every game.
+        let set = PatternSet::new(&["."]);
+        let hits = set.matching_games(FILTER_SAMPLE, &re);
+        assert_eq!(hits.len(), 3);
+    }
+
+    #[test]
+    fn pattern_set_short_literal_is_dropped_and_still_matches() {
+        let re = get_regex().expect("Failed to compile regex");
+        // "d5" is below MIN_LITERAL_LEN, so it can't be used to prefilter, but the
+        // pattern must still run (and correctly match) on every game.
+        let set = PatternSet::new(&["d5"]);
+        let hits = set.matching_games(FILTER_SAMPLE, &re);
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].1.contains("Test Game 2"));
+    }
+
+    #[test]
+    fn pattern_set_handles_overlapping_literals() {
+        let re = get_regex().expect("Failed to compile regex");
+        // "Carl" is a prefix of "Carlsen": a non-overlapping Aho-Corasick scan
+        // would report only "Carl" at that position and never "Carlsen", making
+        // the longer pattern's formula look unsatisfiable even though it
+        // genuinely matches.
+        let set = PatternSet::new(&["Carl", "Carlsen"]);
+        let hits = set.matching_games(FILTER_SAMPLE, &re);
+
+        let carl_hits = hits.iter().filter(|(idx, _)| *idx == 0).count();
+        let carlsen_hits = hits.iter().filter(|(idx, _)| *idx == 1).count();
+        assert_eq!(carl_hits, 3);
+        assert_eq!(carlsen_hits, 3);
+    }
+
+    #[test]
+    fn parse_game_fills_in_the_seven_tag_roster() {
+        let game = r#"[Event "Test Game 1"]
+[Site "?"]
+[Date "2021.01.01"]
+[Round "1"]
+[White "White Player"]
+[Black "Black Player"]
+[Result "1-0"]
+[ECO "C50"]
+
+1. e4 e5 1-0"#;
+        let parsed = parse_game_rs(game);
+
+        assert_eq!(parsed.event, "Test Game 1");
+        assert_eq!(parsed.white, "White Player");
+        assert_eq!(parsed.black, "Black Player");
+        assert_eq!(parsed.result, "1-0");
+        assert_eq!(parsed.tags.get("ECO"), Some(&"C50".to_string()));
+        assert_eq!(
+            parsed.moves,
+            vec![
+                Element::MoveNumber { num: 1, black: false },
+                Element::San("e4".to_string()),
+                Element::San("e5".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_game_defaults_missing_roster_tags() {
+        let game = "[Event \"Lone Event\"]\n\n1. e4 e5 *";
+        let parsed = parse_game_rs(game);
+
+        assert_eq!(parsed.event, "Lone Event");
+        assert_eq!(parsed.site, "?");
+        assert_eq!(parsed.white, "?");
+        assert_eq!(parsed.result, "*");
+    }
+
+    #[test]
+    fn parse_all_parses_every_game_in_a_blob() {
+        let re = get_regex().expect("Failed to compile regex");
+        let games = parse_all_rs(FILTER_SAMPLE, &re);
+
+        assert_eq!(games.len(), 3);
+        assert_eq!(games[0].white, "Magnus Carlsen");
+        assert_eq!(games[2].result, "0-1");
+    }
 }
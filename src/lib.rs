@@ -1,7 +1,10 @@
 pub mod core;
 
+use std::collections::HashMap;
+
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
+use pyo3::types::PyList;
 use pyo3::wrap_pyfunction;
 use crate::core::*;
 use pyo3::types::PyModule;
@@ -45,14 +48,151 @@ fn tag_to_dict(tag: &PgnTag) -> PyResult<PyObject> {
     tag.to_dict()
 }
 
+#[pyfunction]
+fn filter_games(data: &str, query: &str) -> Vec<String> {
+    let re = get_regex().expect("Failed to compile regex");
+    filter_games_rs(data, query, &re)
+}
+
+/// Renders a single movetext [`Element`] as a Python dict, e.g.
+/// `{"type": "san", "san": "e4"}` or `{"type": "variation", "moves": [...]}`.
+fn element_to_py(py: Python<'_>, element: &Element) -> PyResult<PyObject> {
+    let dict = PyDict::new(py);
+    match element {
+        Element::MoveNumber { num, black } => {
+            dict.set_item("type", "move_number")?;
+            dict.set_item("num", num)?;
+            dict.set_item("black", black)?;
+        }
+        Element::San(san) => {
+            dict.set_item("type", "san")?;
+            dict.set_item("san", san)?;
+        }
+        Element::Nag(nag) => {
+            dict.set_item("type", "nag")?;
+            dict.set_item("nag", nag)?;
+        }
+        Element::Comment(text) => {
+            dict.set_item("type", "comment")?;
+            dict.set_item("text", text)?;
+        }
+        Element::Variation(inner) => {
+            dict.set_item("type", "variation")?;
+            dict.set_item("moves", movetext_to_py(py, inner)?)?;
+        }
+    }
+    Ok(dict.into())
+}
+
+/// Renders a [`MoveText`] as a Python list of dicts, see [`element_to_py`].
+fn movetext_to_py(py: Python<'_>, movetext: &MoveText) -> PyResult<PyObject> {
+    let list = PyList::empty(py);
+    for element in movetext {
+        list.append(element_to_py(py, element)?)?;
+    }
+    Ok(list.into())
+}
+
+/// A parsed PGN game, bundling the Seven Tag Roster, the remaining tags, and the
+/// parsed movetext into one idiomatic Python object.
+#[pyclass]
+struct PyGame {
+    inner: Game,
+}
+
+#[pymethods]
+impl PyGame {
+    #[getter]
+    fn event(&self) -> String {
+        self.inner.event.clone()
+    }
+
+    #[getter]
+    fn white(&self) -> String {
+        self.inner.white.clone()
+    }
+
+    #[getter]
+    fn black(&self) -> String {
+        self.inner.black.clone()
+    }
+
+    #[getter]
+    fn result(&self) -> String {
+        self.inner.result.clone()
+    }
+
+    #[getter]
+    fn tags(&self) -> HashMap<String, String> {
+        self.inner.tags.clone()
+    }
+
+    #[getter]
+    fn moves(&self) -> PyResult<PyObject> {
+        Python::with_gil(|py| movetext_to_py(py, &self.inner.moves))
+    }
+
+    fn to_dict(&self) -> PyResult<PyObject> {
+        Python::with_gil(|py| {
+            let dict = PyDict::new(py);
+            dict.set_item("event", &self.inner.event)?;
+            dict.set_item("site", &self.inner.site)?;
+            dict.set_item("date", &self.inner.date)?;
+            dict.set_item("round", &self.inner.round)?;
+            dict.set_item("white", &self.inner.white)?;
+            dict.set_item("black", &self.inner.black)?;
+            dict.set_item("result", &self.inner.result)?;
+            dict.set_item("tags", self.inner.tags.clone())?;
+            dict.set_item("moves", movetext_to_py(py, &self.inner.moves)?)?;
+            Ok(dict.into())
+        })
+    }
+}
+
+#[pyfunction]
+fn parse_game(pgn_string: &str) -> PyGame {
+    PyGame {
+        inner: parse_game_rs(pgn_string),
+    }
+}
+
+#[pyfunction]
+fn validate_result(pgn_string: &str) -> PyResult<PgnTag> {
+    validate_result_rs(pgn_string)
+        .map(|tag| PgnTag {
+            name: tag.name,
+            value: tag.value,
+        })
+        .map_err(|mismatch| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Result tag mismatch: header says {:?} but movetext ends in {:?}",
+                mismatch.header, mismatch.movetext
+            ))
+        })
+}
+
+#[pyfunction]
+fn parse_all(data: &str) -> Vec<PyGame> {
+    let re = get_regex().expect("Failed to compile regex");
+    parse_all_rs(data, &re)
+        .into_iter()
+        .map(|game| PyGame { inner: game })
+        .collect()
+}
+
 /// A Python module implemented in Rust. The name of this function must match
 /// the `lib.name` setting in the `Cargo.toml`, else Python will not be able to
 /// import the module.
 #[pymodule]
 fn _pgn_parser(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PgnTag>()?;
+    m.add_class::<PyGame>()?;
     m.add_function(wrap_pyfunction!(tag_to_dict, m)?)?;
     m.add_function(wrap_pyfunction!(extract_tags, m)?)?;
     m.add_function(wrap_pyfunction!(extract_moves, m)?)?;
+    m.add_function(wrap_pyfunction!(filter_games, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_game, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_all, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_result, m)?)?;
     Ok(())
 }